@@ -0,0 +1,150 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Set of functions to parse `chattr`-style file-attribute change strings.
+
+// spell-checker:ignore (vars) fattr noatime nodump
+
+/// Secure deletion.
+pub const FS_SECRM_FL: u32 = 0x0000_0001;
+/// Undeletable.
+pub const FS_UNRM_FL: u32 = 0x0000_0002;
+/// Compressed.
+pub const FS_COMPR_FL: u32 = 0x0000_0004;
+/// Synchronous updates.
+pub const FS_SYNC_FL: u32 = 0x0000_0008;
+/// Immutable.
+pub const FS_IMMUTABLE_FL: u32 = 0x0000_0010;
+/// Append only.
+pub const FS_APPEND_FL: u32 = 0x0000_0020;
+/// No dump.
+pub const FS_NODUMP_FL: u32 = 0x0000_0040;
+/// No atime updates.
+pub const FS_NOATIME_FL: u32 = 0x0000_0080;
+
+/// Maps each supported attribute letter to its `FS_IOC_SETFLAGS` bit, kept in one place
+/// so a future `lsattr`/`chattr` utility and the permission matcher can share it.
+pub const ATTRIBUTE_LETTERS: &[(char, u32)] = &[
+    ('s', FS_SYNC_FL),
+    ('u', FS_UNRM_FL),
+    ('c', FS_COMPR_FL),
+    ('i', FS_IMMUTABLE_FL),
+    ('a', FS_APPEND_FL),
+    ('d', FS_NODUMP_FL),
+    ('A', FS_NOATIME_FL),
+];
+
+fn letter_to_flag(ch: char) -> Option<u32> {
+    ATTRIBUTE_LETTERS
+        .iter()
+        .find(|&&(letter, _)| letter == ch)
+        .map(|&(_, flag)| flag)
+}
+
+fn parse_op(spec: &str) -> Result<(char, usize), String> {
+    let ch = spec
+        .chars()
+        .next()
+        .ok_or_else(|| "unexpected end of attribute spec".to_owned())?;
+    match ch {
+        '+' | '-' | '=' => Ok((ch, 1)),
+        _ => Err(format!(
+            "invalid operator (expected +, -, or =, but found {ch})"
+        )),
+    }
+}
+
+fn parse_letters(spec: &str) -> Result<u32, String> {
+    if spec.is_empty() {
+        return Err("unexpected end of attribute spec".to_owned());
+    }
+    let mut flags = 0;
+    for ch in spec.chars() {
+        let flag = letter_to_flag(ch).ok_or_else(|| {
+            let letters: String = ATTRIBUTE_LETTERS.iter().map(|&(l, _)| l).collect();
+            format!("invalid attribute letter (expected one of {letters}, but found {ch})")
+        })?;
+        flags |= flag;
+    }
+    Ok(flags)
+}
+
+/// Parse a `chattr`-style attribute spec (e.g. `+i`, `-a`, `=d`, or the comma-separated
+/// `+ai,-s`) and apply it to `current`, returning the resulting flag bitmask.
+///
+/// Mirrors [`super::mode::parse`]: comma-separated parts are applied to `current` in
+/// sequence, each part being an operator (`+`, `-`, or `=`) followed by one or more
+/// attribute letters. `+` ORs the letters' bits in, `-` clears them, and `=` resets the
+/// bitmask to exactly the given set.
+pub fn parse_attributes(current: u32, spec: &str) -> Result<u32, String> {
+    let mut attrs = current;
+    for part in spec.split(',') {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        let (op, pos) = parse_op(part)?;
+        let flags = parse_letters(&part[pos..])?;
+        attrs = match op {
+            '+' => attrs | flags,
+            '-' => attrs & !flags,
+            '=' => flags,
+            _ => unreachable!(),
+        };
+    }
+    Ok(attrs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_attribute() {
+        assert_eq!(parse_attributes(0, "+i").unwrap(), FS_IMMUTABLE_FL);
+        assert_eq!(parse_attributes(0, "+a").unwrap(), FS_APPEND_FL);
+    }
+
+    #[test]
+    fn test_parse_clear_attribute() {
+        let current = FS_IMMUTABLE_FL | FS_APPEND_FL;
+        assert_eq!(parse_attributes(current, "-a").unwrap(), FS_IMMUTABLE_FL);
+    }
+
+    #[test]
+    fn test_parse_set_exact() {
+        let current = FS_IMMUTABLE_FL | FS_APPEND_FL;
+        assert_eq!(parse_attributes(current, "=d").unwrap(), FS_NODUMP_FL);
+    }
+
+    #[test]
+    fn test_parse_multiple_letters() {
+        assert_eq!(
+            parse_attributes(0, "+ai").unwrap(),
+            FS_APPEND_FL | FS_IMMUTABLE_FL
+        );
+    }
+
+    #[test]
+    fn test_parse_comma_separated() {
+        let result = parse_attributes(0, "+i,+a,-i").unwrap();
+        assert_eq!(result, FS_APPEND_FL);
+    }
+
+    #[test]
+    fn test_parse_unknown_letter_is_error() {
+        assert!(parse_attributes(0, "+z").is_err());
+    }
+
+    #[test]
+    fn test_parse_missing_operator_is_error() {
+        assert!(parse_attributes(0, "i").is_err());
+    }
+
+    #[test]
+    fn test_parse_missing_letters_is_error() {
+        assert!(parse_attributes(0, "+").is_err());
+    }
+}
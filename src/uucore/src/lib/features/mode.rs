@@ -177,6 +177,152 @@ pub fn parse(
     }
 }
 
+/// Like [`parse`], but also understands `--reference=RFILE`: when `reference` is given
+/// and `mode` is `None`, the result is simply the reference file's `0o7777` bits,
+/// matching GNU `chmod --reference`. `mode` and `reference` are mutually exclusive, as
+/// in GNU `chmod`; if a caller somehow has both, `mode` takes precedence.
+pub fn parse_with_reference(
+    current_mode: u32,
+    mode: Option<&str>,
+    considering_dir: bool,
+    umask: Option<u32>,
+    reference: Option<u32>,
+) -> Result<u32, String> {
+    match (mode, reference) {
+        (None, Some(reference_mode)) => Ok(reference_mode & 0o7777),
+        _ => parse(current_mode, mode, considering_dir, umask),
+    }
+}
+
+/// The three ways a `-perm`-style spec can be matched against an existing mode,
+/// mirroring the leading sigil used by `find -perm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermMatchKind {
+    /// Bare `MODE`: the mode must equal the spec exactly.
+    Exact,
+    /// `-MODE`: the mode must have all of the spec's bits set.
+    All,
+    /// `/MODE`: the mode must have any of the spec's bits set.
+    Any,
+}
+
+/// A compiled `find -perm`-style mode spec, ready to be tested against a file's mode.
+///
+/// Symbolic specs such as `a+X` can resolve differently for files and
+/// directories, so the target is precomputed for both and [`PermMatcher::matches`]
+/// picks the right one based on `is_dir`.
+#[derive(Debug, Clone, Copy)]
+pub struct PermMatcher {
+    kind: PermMatchKind,
+    file_mode: u32,
+    dir_mode: u32,
+}
+
+impl PermMatcher {
+    /// Test whether `mode` (the low 12 bits of which are significant) satisfies this spec.
+    pub fn matches(&self, mode: u32, is_dir: bool) -> bool {
+        let mode = mode & 0o7777;
+        let target = if is_dir { self.dir_mode } else { self.file_mode };
+        match self.kind {
+            PermMatchKind::Exact => mode == target,
+            PermMatchKind::All => mode & target == target,
+            PermMatchKind::Any => target == 0 || mode & target != 0,
+        }
+    }
+}
+
+/// Parse a `find -perm`-style spec (e.g. `644`, `-644`, `/222`, `-u+w`) into a [`PermMatcher`].
+///
+/// The leading sigil selects the match kind: `-MODE` requires all of the given bits,
+/// `/MODE` requires any of them, and a bare `MODE` requires an exact match. The body
+/// may be numeric or symbolic and is parsed the same way as [`parse_numeric`] and
+/// [`parse_symbolic`], starting from a zero base.
+pub fn parse_matcher(spec: &str) -> Result<PermMatcher, String> {
+    let (kind, body) = match spec.chars().next() {
+        Some('-') => (PermMatchKind::All, &spec[1..]),
+        Some('/') => (PermMatchKind::Any, &spec[1..]),
+        _ => (PermMatchKind::Exact, spec),
+    };
+    if body.is_empty() {
+        return Err(format!("invalid mode ({spec})"));
+    }
+    let target = |considering_dir: bool| -> Result<u32, String> {
+        if body.chars().any(|c| c.is_ascii_digit()) {
+            parse_numeric(0, body, considering_dir)
+        } else {
+            parse_symbolic(0, body, 0, considering_dir)
+        }
+    };
+    Ok(PermMatcher {
+        kind,
+        file_mode: target(false)?,
+        dir_mode: target(true)?,
+    })
+}
+
+/// Render `mode` as a 9-character `ls`-style `rwx` string, collapsing the setuid/setgid
+/// bits into `s`/`S` in the user/group execute column and the sticky bit into `t`/`T`
+/// in the other execute column (uppercase when the underlying execute bit is clear).
+pub fn mode_to_rwx(mode: u32) -> String {
+    let classes = [(6, 0o4000), (3, 0o2000), (0, 0o1000)];
+    let mut rwx = String::with_capacity(9);
+    for (i, (shift, special)) in classes.into_iter().enumerate() {
+        let bits = (mode >> shift) & 0o7;
+        rwx.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        rwx.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        let exec = bits & 0o1 != 0;
+        let is_sticky = i == 2;
+        rwx.push(match (mode & special != 0, exec, is_sticky) {
+            (true, true, true) => 't',
+            (true, false, true) => 'T',
+            (true, true, false) => 's',
+            (true, false, false) => 'S',
+            (false, true, _) => 'x',
+            (false, false, _) => '-',
+        });
+    }
+    rwx
+}
+
+/// Render `mode` as a comma-separated symbolic spec (e.g. `u=rwx,g=rx,o=rx`) that is
+/// round-trippable through [`parse`].
+pub fn mode_to_symbolic(mode: u32) -> String {
+    let classes = [('u', 6, 0o4000, 's'), ('g', 3, 0o2000, 's'), ('o', 0, 0o1000, 't')];
+    classes
+        .into_iter()
+        .map(|(who, shift, special, special_letter)| {
+            let bits = (mode >> shift) & 0o7;
+            let mut perm = String::new();
+            if bits & 0o4 != 0 {
+                perm.push('r');
+            }
+            if bits & 0o2 != 0 {
+                perm.push('w');
+            }
+            if bits & 0o1 != 0 {
+                perm.push('x');
+            }
+            if mode & special != 0 {
+                perm.push(special_letter);
+            }
+            format!("{who}={perm}")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Describe a mode change in the `OCTAL (RWX) to OCTAL (RWX)` form used by GNU
+/// `chmod --verbose` and similar diagnostics, e.g. `0644 (rw-r--r--) to 0755 (rwxr-xr-x)`.
+pub fn describe_change(old: u32, new: u32) -> String {
+    format!(
+        "{:04o} ({}) to {:04o} ({})",
+        old & 0o7777,
+        mode_to_rwx(old),
+        new & 0o7777,
+        mode_to_rwx(new)
+    )
+}
+
 pub fn get_umask() -> u32 {
     // There's no portable way to read the umask without changing it.
     // We have to replace it and then quickly set it back, hopefully before
@@ -207,7 +353,9 @@ pub fn get_umask() -> u32 {
 
 #[cfg(test)]
 mod test {
-    use super::parse;
+    use super::{
+        describe_change, mode_to_rwx, mode_to_symbolic, parse, parse_matcher, parse_with_reference,
+    };
 
     #[test]
     fn symbolic_modes() {
@@ -363,4 +511,126 @@ mod test {
         // First add user write, then set to 755 (should override)
         assert_eq!(parse(0, Some("u+w,755"), false, Some(0)).unwrap(), 0o755);
     }
+
+    #[test]
+    fn test_parse_matcher_exact() {
+        let m = parse_matcher("644").unwrap();
+        assert!(m.matches(0o644, false));
+        assert!(!m.matches(0o600, false));
+        assert!(!m.matches(0o755, false));
+    }
+
+    #[test]
+    fn test_parse_matcher_all() {
+        let m = parse_matcher("-222").unwrap();
+        assert!(m.matches(0o222, false));
+        assert!(m.matches(0o666, false));
+        assert!(!m.matches(0o644, false));
+
+        // -000 matches everything
+        let m = parse_matcher("-000").unwrap();
+        assert!(m.matches(0o000, false));
+        assert!(m.matches(0o777, false));
+    }
+
+    #[test]
+    fn test_parse_matcher_any() {
+        let m = parse_matcher("/222").unwrap();
+        assert!(m.matches(0o200, false));
+        assert!(m.matches(0o020, false));
+        assert!(!m.matches(0o555, false));
+
+        // /000 matches everything
+        let m = parse_matcher("/000").unwrap();
+        assert!(m.matches(0o000, false));
+        assert!(m.matches(0o777, false));
+    }
+
+    #[test]
+    fn test_parse_matcher_symbolic() {
+        let m = parse_matcher("-u+w").unwrap();
+        assert!(m.matches(0o622, false));
+        assert!(!m.matches(0o444, false));
+    }
+
+    #[test]
+    fn test_parse_matcher_empty_is_error() {
+        assert!(parse_matcher("").is_err());
+        assert!(parse_matcher("-").is_err());
+        assert!(parse_matcher("/").is_err());
+    }
+
+    #[test]
+    fn test_mode_to_rwx() {
+        assert_eq!(mode_to_rwx(0o644), "rw-r--r--");
+        assert_eq!(mode_to_rwx(0o755), "rwxr-xr-x");
+        assert_eq!(mode_to_rwx(0o000), "---------");
+        assert_eq!(mode_to_rwx(0o4755), "rwsr-xr-x");
+        assert_eq!(mode_to_rwx(0o4655), "rwSr-xr-x");
+        assert_eq!(mode_to_rwx(0o2755), "rwxr-sr-x");
+        assert_eq!(mode_to_rwx(0o1755), "rwxr-xr-t");
+        assert_eq!(mode_to_rwx(0o1754), "rwxr-xr-T");
+    }
+
+    #[test]
+    fn test_mode_to_symbolic_round_trip() {
+        for mode in [0o644, 0o755, 0o000, 0o4755, 0o2750, 0o1777] {
+            let symbolic = mode_to_symbolic(mode);
+            assert_eq!(parse(0, Some(&symbolic), false, Some(0)).unwrap(), mode);
+        }
+    }
+
+    #[test]
+    fn test_describe_change() {
+        assert_eq!(
+            describe_change(0o644, 0o755),
+            "0644 (rw-r--r--) to 0755 (rwxr-xr-x)"
+        );
+    }
+
+    #[test]
+    fn test_parse_permcopy_single_clause() {
+        // o=g: other becomes a copy of the current group bits
+        assert_eq!(parse(0o640, Some("o=g"), false, Some(0)).unwrap(), 0o644);
+        // g=u: group becomes a copy of the current user bits
+        assert_eq!(parse(0o640, Some("g=u"), false, Some(0)).unwrap(), 0o660);
+    }
+
+    #[test]
+    fn test_parse_permcopy_chained_clauses() {
+        // g=u then o=g must apply left-to-right against the running mode: group first
+        // becomes a copy of user (0o660), then other becomes a copy of that new group.
+        assert_eq!(
+            parse(0o640, Some("g=u,o=g"), false, Some(0)).unwrap(),
+            0o666
+        );
+    }
+
+    #[test]
+    fn test_parse_special_bits_respect_level_mask() {
+        // u+s/g+s only ever touch their own special bit, never the other's.
+        assert_eq!(
+            parse(0o644, Some("u+s"), false, Some(0)).unwrap(),
+            0o4644
+        );
+        assert_eq!(
+            parse(0o644, Some("g+s"), false, Some(0)).unwrap(),
+            0o2644
+        );
+    }
+
+    #[test]
+    fn test_parse_with_reference() {
+        // With no mode given, the result is exactly the reference's permission bits,
+        // masked out of e.g. a full `st_mode` that also carries the file-type bits.
+        assert_eq!(
+            parse_with_reference(0o000, None, false, Some(0), Some(0o100751)).unwrap(),
+            0o751
+        );
+        // If both are given, the MODE argument takes precedence over the reference.
+        assert_eq!(
+            parse_with_reference(0o000, Some("644"), false, Some(0), Some(0o777)).unwrap(),
+            0o644
+        );
+    }
 }